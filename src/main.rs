@@ -1,21 +1,29 @@
 // secure_delete - minimal secure file shredder (Rust)
 // Usage:
 //   cargo build --release
-//   ./target/release/secure_delete <file> [--passes N] [--pattern zeros|ones|random] [--confirm]
+//   ./target/release/secure_delete <file|dir|device> [file|dir|device ...] [--recursive]
+//       [--passes N] [--pattern zeros|ones|random] [--scheme gutmann|dod|dod7|random]
+//       [--zero] [--exact] [--force] [--verbose] [--quiet] [--confirm]
 // Example:
-//   secure_delete secret.zip --passes 3 --pattern random --confirm
+//   secure_delete secret.zip --scheme dod --zero --confirm
 //
 // Notes:
 // - Overwrites file contents in chunks (8 MiB by default).
-// - After overwriting passes, renames file to a random name in same directory, optionally attempts to clear readonly bit, then removes file.
-// - Cross-platform behavior: uses only std + rand; tries to set writable permissions before unlinking.
+// - After overwriting passes, progressively renames the file to shorter and shorter
+//   names in the same directory (fsyncing the directory after each rename), then
+//   removes it. Directories are walked recursively with --recursive; block/character
+//   devices can be wiped whole with --force.
+// - Unless --exact is given, the final pass is rounded up to the filesystem block size
+//   so slack space in the file's last allocated block is overwritten too.
+// - Cross-platform behavior: uses only std + rand; tries to set writable permissions
+//   before unlinking. Device wiping and block-size slack wiping are Unix-only.
 
 use rand::{rngs::OsRng, RngCore};
 use std::env;
 use std::fs::{self, File, OpenOptions};
-use std::io::{self, Seek, SeekFrom, Write};
+use std::io::{self, IsTerminal, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
 const DEFAULT_PASSES: usize = 3;
 const CHUNK_SIZE: usize = 8 * 1024 * 1024; // 8 MiB chunk writes
@@ -38,9 +46,182 @@ impl Pattern {
     }
 }
 
+/// A single overwrite pass: either cryptographically random bytes, or a
+/// fixed (possibly multi-byte) sequence tiled across the write buffer.
+#[derive(Debug, Clone)]
+enum PassSpec {
+    Random,
+    Bytes(Vec<u8>),
+    /// An all-zero pass appended after the configured scheme (see `--zero`).
+    /// Behaves identically to `Bytes(vec![0x00])` but is labeled distinctly.
+    FinalZero,
+}
+
+impl PassSpec {
+    fn label(&self) -> String {
+        match self {
+            PassSpec::Random => "random".to_string(),
+            PassSpec::Bytes(b) => {
+                let mut s = String::from("0x");
+                for byte in b {
+                    s.push_str(&format!("{:02x}", byte));
+                }
+                s
+            }
+            PassSpec::FinalZero => "final zeroing pass".to_string(),
+        }
+    }
+}
+
+/// A pass together with whether it must be read back and compared against
+/// what was written. Every pass of the DoD 5220.22-M schemes (3-pass and the
+/// 7-pass "ECE" variant) is verified; other schemes don't call for it.
+#[derive(Debug, Clone)]
+struct Pass {
+    spec: PassSpec,
+    verify: bool,
+}
+
+impl Pass {
+    fn unverified(spec: PassSpec) -> Self {
+        Pass { spec, verify: false }
+    }
+
+    fn verified(spec: PassSpec) -> Self {
+        Pass { spec, verify: true }
+    }
+}
+
+/// Preset multi-pass wipe schemes, as an alternative to a single repeated `Pattern`.
+#[derive(Debug, Clone, Copy)]
+enum Scheme {
+    Gutmann,
+    Dod,
+    Dod7,
+    Random,
+}
+
+impl Scheme {
+    fn from_str(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "gutmann" => Some(Scheme::Gutmann),
+            "dod" => Some(Scheme::Dod),
+            "dod7" => Some(Scheme::Dod7),
+            "random" => Some(Scheme::Random),
+            _ => None,
+        }
+    }
+
+    /// Build the ordered sequence of passes for this scheme. `passes` is only
+    /// consulted by `Scheme::Random`, which has no fixed pass count of its own.
+    /// DoD's passes are marked for read-back verification; the others aren't.
+    fn pass_specs(self, passes: usize) -> Vec<Pass> {
+        match self {
+            Scheme::Random => vec![Pass::unverified(PassSpec::Random); passes.max(1)],
+            Scheme::Dod => vec![
+                Pass::verified(PassSpec::Bytes(vec![0x00])),
+                Pass::verified(PassSpec::Bytes(vec![0xFF])),
+                Pass::verified(PassSpec::Random),
+            ],
+            Scheme::Dod7 => vec![
+                Pass::verified(PassSpec::Bytes(vec![0x00])),
+                Pass::verified(PassSpec::Bytes(vec![0xFF])),
+                Pass::verified(PassSpec::Random),
+                Pass::verified(PassSpec::Bytes(vec![0x00])),
+                Pass::verified(PassSpec::Bytes(vec![0xFF])),
+                Pass::verified(PassSpec::Random),
+                Pass::verified(PassSpec::Random),
+            ],
+            Scheme::Gutmann => {
+                let mut specs = Vec::with_capacity(35);
+                // Passes 1-4: random
+                for _ in 0..4 {
+                    specs.push(PassSpec::Random);
+                }
+                // Passes 5-6: single-byte fills
+                specs.push(PassSpec::Bytes(vec![0x55]));
+                specs.push(PassSpec::Bytes(vec![0xAA]));
+                // Passes 7-9: rotations of the 0x92/0x49/0x24 cycle
+                specs.push(PassSpec::Bytes(vec![0x92, 0x49, 0x24]));
+                specs.push(PassSpec::Bytes(vec![0x49, 0x24, 0x92]));
+                specs.push(PassSpec::Bytes(vec![0x24, 0x92, 0x49]));
+                // Passes 10-25: the full 0x00..=0xFF ladder, stepping by 0x11
+                for i in 0..16u8 {
+                    specs.push(PassSpec::Bytes(vec![i * 0x11]));
+                }
+                // Passes 26-28: rotations of the 0x92/0x49/0x24 cycle again
+                specs.push(PassSpec::Bytes(vec![0x92, 0x49, 0x24]));
+                specs.push(PassSpec::Bytes(vec![0x49, 0x24, 0x92]));
+                specs.push(PassSpec::Bytes(vec![0x24, 0x92, 0x49]));
+                // Passes 29-31: rotations of the 0x6D/0xB6/0xDB cycle
+                specs.push(PassSpec::Bytes(vec![0x6D, 0xB6, 0xDB]));
+                specs.push(PassSpec::Bytes(vec![0xB6, 0xDB, 0x6D]));
+                specs.push(PassSpec::Bytes(vec![0xDB, 0x6D, 0xB6]));
+                // Passes 32-35: random
+                for _ in 0..4 {
+                    specs.push(PassSpec::Random);
+                }
+                specs.into_iter().map(Pass::unverified).collect()
+            }
+        }
+    }
+}
+
+/// Build the pass sequence for the legacy `--pattern`/`--passes` combination:
+/// the same pattern repeated `passes` times.
+fn pass_specs_for_pattern(pattern: Pattern, passes: usize) -> Vec<Pass> {
+    let spec = match pattern {
+        Pattern::Zeros => PassSpec::Bytes(vec![0x00]),
+        Pattern::Ones => PassSpec::Bytes(vec![0xFF]),
+        Pattern::Random => PassSpec::Random,
+    };
+    vec![Pass::unverified(spec); passes.max(1)]
+}
+
+/// Fill `buf` by tiling `pattern` across it, repeating from the start as needed.
+fn fill_tiled(buf: &mut [u8], pattern: &[u8]) {
+    if pattern.len() == 1 {
+        // Fast path: uniform fill.
+        for b in buf.iter_mut() {
+            *b = pattern[0];
+        }
+        return;
+    }
+    for (i, b) in buf.iter_mut().enumerate() {
+        *b = pattern[i % pattern.len()];
+    }
+}
+
+/// Best-effort hint to the kernel to drop cached pages for `file` covering
+/// `[offset, offset + len)`, so a subsequent read is forced back to the
+/// underlying storage instead of being served from the page cache. Only
+/// meaningful (and only implemented) on Linux; a no-op elsewhere, and
+/// failures here are not fatal since the caller's verification read is what
+/// actually matters.
+#[cfg(target_os = "linux")]
+fn drop_cached_pages(file: &File, offset: u64, len: u64) {
+    use std::os::unix::io::AsRawFd;
+    const POSIX_FADV_DONTNEED: std::os::raw::c_int = 4;
+    extern "C" {
+        fn posix_fadvise(
+            fd: std::os::unix::io::RawFd,
+            offset: i64,
+            len: i64,
+            advice: std::os::raw::c_int,
+        ) -> std::os::raw::c_int;
+    }
+    let _ = unsafe { posix_fadvise(file.as_raw_fd(), offset as i64, len as i64, POSIX_FADV_DONTNEED) };
+}
+
+#[cfg(not(target_os = "linux"))]
+fn drop_cached_pages(_file: &File, _offset: u64, _len: u64) {}
+
 fn print_usage_and_exit(program: &str) -> ! {
-    eprintln!("Usage: {} <file> [--passes N] [--pattern zeros|ones|random] [--confirm]", program);
-    eprintln!("Example: {} secret.zip --passes 3 --pattern random --confirm", program);
+    eprintln!(
+        "Usage: {} <file|dir|device> [file|dir|device ...] [--recursive] [--passes N] [--pattern zeros|ones|random] [--scheme gutmann|dod|dod7|random] [--zero] [--exact] [--force] [--verbose] [--quiet] [--confirm]",
+        program
+    );
+    eprintln!("Example: {} secret.zip --scheme dod --confirm", program);
     std::process::exit(1);
 }
 
@@ -83,55 +264,106 @@ fn ensure_writable(path: &Path) {
     }
 }
 
-/// Overwrite the file at `path` with the specified pattern for `passes` times.
-/// Uses chunked writes and syncs to disk after each pass.
-/// Returns Ok(()) on success; io::Error on failure.
-fn overwrite_file(path: &Path, passes: usize, pattern: Pattern) -> io::Result<()> {
-    let metadata = fs::metadata(path)?;
-    let file_size = metadata.len();
-    if file_size == 0 {
-        // nothing to do but still try to unlink later
-        return Ok(());
+/// Returns the size (in bytes) that the final pass should cover: `file_size`
+/// when `exact`, otherwise `file_size` rounded up to the filesystem's block
+/// size so the tail/slack of the last allocated block is overwritten too.
+fn target_size_for_final_pass(metadata: &fs::Metadata, file_size: u64, exact: bool) -> u64 {
+    if exact {
+        return file_size;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let blksize = metadata.blksize().max(1);
+        let rem = file_size % blksize;
+        if rem == 0 {
+            file_size
+        } else {
+            file_size + (blksize - rem)
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        file_size
     }
+}
 
-    // Pre-prepare a static chunk buffer for zeros/ones to avoid repeated allocations
-    let zeros = vec![0u8; CHUNK_SIZE];
-    let ones = vec![0xFFu8; CHUNK_SIZE];
+/// Controls how much progress output `write_passes` emits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+}
 
-    // For random we will generate into a buffer each time.
+/// Run every `PassSpec` over `file` in order, each pass covering `file_size` bytes
+/// except the last, which covers `target_size` (equal to `file_size` unless the
+/// caller rounded up to cover trailing slack space). Uses chunked writes and
+/// syncs to disk after each pass. Shared by file and whole-device wiping.
+fn write_passes(
+    file: &mut File,
+    file_size: u64,
+    target_size: u64,
+    passes: &[Pass],
+    verbosity: Verbosity,
+) -> io::Result<()> {
     let mut rng = OsRng;
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut verify_buf = vec![0u8; CHUNK_SIZE];
 
-    // We'll open the file for write access.
-    let mut file = OpenOptions::new().write(true).open(path)?;
-
-    // For progress reporting:
-    let total_bytes = file_size.checked_mul(passes as u64).unwrap_or(u64::MAX);
     let mut bytes_written_total: u64 = 0;
     let t0 = Instant::now();
+    let is_tty = io::stderr().is_terminal();
+
+    for (pass_idx, pass) in passes.iter().enumerate() {
+        let spec = &pass.spec;
+        let is_last = pass_idx + 1 == passes.len();
+        let pass_size = if is_last { target_size } else { file_size };
+        if is_last && target_size > file_size {
+            file.set_len(target_size)?;
+        }
 
-    for pass in 0..passes {
         // Seek to start
         file.seek(SeekFrom::Start(0))?;
 
-        let mut remaining = file_size;
+        let mut remaining = pass_size;
         while remaining > 0 {
             let to_write = std::cmp::min(remaining, CHUNK_SIZE as u64) as usize;
-            let buf: &[u8];
 
-            match pattern {
-                Pattern::Zeros => {
-                    buf = &zeros[..to_write];
-                    file.write_all(buf)?;
+            match spec {
+                PassSpec::Random => {
+                    rng.fill_bytes(&mut buf[..to_write]);
                 }
-                Pattern::Ones => {
-                    buf = &ones[..to_write];
-                    file.write_all(buf)?;
+                PassSpec::Bytes(pattern) => {
+                    fill_tiled(&mut buf[..to_write], pattern);
                 }
-                Pattern::Random => {
-                    // fill a local buffer with random bytes and write
-                    let mut rb = vec![0u8; to_write];
-                    rng.fill_bytes(&mut rb);
-                    file.write_all(&rb)?;
+                PassSpec::FinalZero => {
+                    fill_tiled(&mut buf[..to_write], &[0x00]);
+                }
+            }
+            file.write_all(&buf[..to_write])?;
+
+            if pass.verify {
+                // Force this chunk to the underlying storage *before* reading
+                // it back, and hint the kernel to drop its cached pages for
+                // the range we're about to re-read — otherwise the read-back
+                // is just served from the page cache and can't catch writes
+                // the OS/disk silently dropped. Seeking back by `to_write`
+                // and reading forward again lands the file position exactly
+                // where the write left it.
+                file.sync_all()?;
+                let written_at = file.stream_position()? - to_write as u64;
+                drop_cached_pages(file, written_at, to_write as u64);
+                file.seek(SeekFrom::Current(-(to_write as i64)))?;
+                file.read_exact(&mut verify_buf[..to_write])?;
+                if verify_buf[..to_write] != buf[..to_write] {
+                    return Err(io::Error::other(format!(
+                        "verification failed on pass {}/{} ({})",
+                        pass_idx + 1,
+                        passes.len(),
+                        spec.label()
+                    )));
                 }
             }
 
@@ -142,101 +374,240 @@ fn overwrite_file(path: &Path, passes: usize, pattern: Pattern) -> io::Result<()
         // Force writes to disk
         file.sync_all()?;
 
-        // small pause so progress prints nicely on very fast SSDs
-        std::thread::sleep(Duration::from_millis(50));
+        if verbosity == Verbosity::Quiet {
+            continue;
+        }
 
         let elapsed = t0.elapsed();
-        // crude progress line
-        eprint!(
-            "\rPass {}/{} completed (elapsed: {:.1}s). Total bytes written: {}       ",
-            pass + 1,
-            passes,
-            elapsed.as_secs_f64(),
-            bytes_written_total
-        );
+        let line = if verbosity == Verbosity::Verbose {
+            let mib_written = bytes_written_total as f64 / (1024.0 * 1024.0);
+            let mib_per_sec = mib_written / elapsed.as_secs_f64().max(f64::EPSILON);
+            format!(
+                "pass {}/{}: {} ({:.1} MiB/s, {} bytes written, elapsed {:.1}s)",
+                pass_idx + 1,
+                passes.len(),
+                spec.label(),
+                mib_per_sec,
+                bytes_written_total,
+                elapsed.as_secs_f64()
+            )
+        } else {
+            format!(
+                "Pass {}/{} ({}) completed (elapsed: {:.1}s). Total bytes written: {}       ",
+                pass_idx + 1,
+                passes.len(),
+                spec.label(),
+                elapsed.as_secs_f64(),
+                bytes_written_total
+            )
+        };
+
+        // Verbose mode prints one line per pass so each pass's identity stays
+        // visible; Normal mode rewrites the same line in place on a TTY and
+        // falls back to plain lines when redirected/logged.
+        if verbosity == Verbosity::Verbose {
+            eprintln!("{}", line);
+        } else if is_tty {
+            eprint!("\r{}", line);
+        } else {
+            eprintln!("{}", line);
+        }
     }
 
-    // final newline after progress
-    eprintln!();
+    if verbosity == Verbosity::Normal && is_tty {
+        // final newline after the carriage-returned progress
+        eprintln!();
+    }
 
     Ok(())
 }
 
-/// Generate a random filename of the given length in same directory.
-/// Returns the new PathBuf (existing file not created).
-fn random_filename_in_same_dir(orig: &Path, len: usize) -> PathBuf {
-    let mut name = String::with_capacity(len);
-    let mut rng = OsRng;
-    const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
-    for _ in 0..len {
-        let idx = (rng.next_u32() as usize) % CHARS.len();
-        name.push(CHARS[idx] as char);
-    }
-
-    // preserve extension if present (replace name but keep extension)
-    let mut new = orig.to_path_buf();
-    if let Some(ext) = orig.extension() {
-        let mut file_name = name;
-        file_name.push('.');
-        file_name.push_str(&ext.to_string_lossy());
-        new.set_file_name(file_name);
-    } else {
-        new.set_file_name(name);
+/// Overwrite the file at `path`, running each `PassSpec` in order as a full pass
+/// over the file's contents. Unless `exact` is set, the final pass is extended to
+/// the next filesystem block boundary so slack space in the file's last allocated
+/// block is destroyed too. Returns Ok(()) on success; io::Error on failure.
+fn overwrite_file(path: &Path, passes: &[Pass], exact: bool, verbosity: Verbosity) -> io::Result<()> {
+    let metadata = fs::metadata(path)?;
+    let file_size = metadata.len();
+    if file_size == 0 {
+        // nothing to do but still try to unlink later
+        return Ok(());
     }
-    new
+    let target_size = target_size_for_final_pass(&metadata, file_size, exact);
+
+    // Opened for read as well as write so verified passes can read back what
+    // they just wrote.
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    write_passes(&mut file, file_size, target_size, passes, verbosity)
 }
 
-fn rename_to_random_and_unlink(path: &Path) -> io::Result<()> {
-    // Attempt to rename file to random filename (same dir) several times
-    let parent = path.parent().unwrap_or_else(|| Path::new("."));
-    // choose random name length relative to original name length
-    let orig_name_len = path.file_name().and_then(|s| s.to_str()).map(|s| s.len()).unwrap_or(12);
-    let mut attempts = 0usize;
-    let max_attempts = 8;
-    loop {
-        let candidate = random_filename_in_same_dir(path, std::cmp::max(8, orig_name_len));
-        let candidate_path = parent.join(candidate.file_name().unwrap());
-        // Try to rename; if target exists, retry
-        let res = fs::rename(path, &candidate_path);
-        match res {
-            Ok(_) => {
-                // Set permissions to owner-write only (best-effort)
-                #[cfg(unix)]
-                {
-                    use std::os::unix::fs::PermissionsExt;
-                    let _ = fs::set_permissions(&candidate_path, fs::Permissions::from_mode(0o600));
-                }
-                #[cfg(windows)]
-                {
-                    let mut perm = fs::metadata(&candidate_path)?.permissions();
-                    perm.set_readonly(false);
-                    let _ = fs::set_permissions(&candidate_path, perm);
-                }
+/// True if `path` is a block or character special file (a raw device) rather
+/// than a regular file.
+fn is_special_device(path: &Path) -> io::Result<bool> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        let ft = fs::metadata(path)?.file_type();
+        Ok(ft.is_block_device() || ft.is_char_device())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Ok(false)
+    }
+}
 
-                // Finally remove file
-                return fs::remove_file(&candidate_path);
-            }
-            Err(e) => {
-                attempts += 1;
-                if attempts >= max_attempts {
-                    return Err(e);
-                }
-                // small jitter and retry
-                std::thread::sleep(Duration::from_millis(20));
+/// `_IOR(0x12, 114, size_t)` from `<linux/fs.h>`: asks the kernel for the size
+/// in bytes of the block device backing `fd`. `metadata.len()` is always 0 for
+/// device nodes, so this is the only reliable way to learn their capacity.
+#[cfg(target_os = "linux")]
+fn blkgetsize64(fd: std::os::unix::io::RawFd) -> io::Result<u64> {
+    const BLKGETSIZE64: std::os::raw::c_ulong = 0x80081272;
+    extern "C" {
+        fn ioctl(fd: std::os::unix::io::RawFd, request: std::os::raw::c_ulong, ...) -> std::os::raw::c_int;
+    }
+    let mut size: u64 = 0;
+    let ret = unsafe { ioctl(fd, BLKGETSIZE64, &mut size as *mut u64) };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(size)
+}
+
+/// Determine the capacity of a block/character device. On Linux, tries the
+/// `BLKGETSIZE64` ioctl first; otherwise (and as a fallback) seeks to the end
+/// of the device to find its size.
+fn device_size(file: &mut File) -> io::Result<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::io::AsRawFd;
+        if let Ok(size) = blkgetsize64(file.as_raw_fd()) {
+            if size > 0 {
+                return Ok(size);
             }
         }
     }
+    file.seek(SeekFrom::End(0))
 }
 
-fn process_path(path: &Path, passes: usize, pattern: Pattern, require_confirm: bool) -> io::Result<()> {
-    if !path.exists() {
-        return Err(io::Error::new(io::ErrorKind::NotFound, "file not found"));
+/// Overwrite an entire block/character device with the configured passes.
+/// Unlike `overwrite_file`, there is no logical size vs. slack distinction
+/// (the whole device is the target) and the device is never renamed or
+/// unlinked afterwards, since doing so would destroy the special file itself.
+fn overwrite_device(path: &Path, passes: &[Pass], verbosity: Verbosity) -> io::Result<()> {
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let device_bytes = device_size(&mut file)?;
+    if device_bytes == 0 {
+        return Err(io::Error::other("could not determine device size"));
     }
-    if path.is_dir() {
-        return Err(io::Error::new(io::ErrorKind::Other, "path is a directory; secure_delete handles files only"));
+    write_passes(&mut file, device_bytes, device_bytes, passes, verbosity)
+}
+
+/// Characters used for the progressively-shortened filenames in `wipename`.
+const WIPENAME_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Advance the last character of `indices`, carrying over into earlier
+/// positions like an odometer. Returns `false` once every combination of
+/// this length has been tried (all positions rolled back to 0).
+fn advance_name(indices: &mut [usize], base: usize) -> bool {
+    for i in (0..indices.len()).rev() {
+        indices[i] += 1;
+        if indices[i] < base {
+            return true;
+        }
+        indices[i] = 0;
     }
+    false
+}
 
-    if require_confirm {
+/// Rename `from` to the shortest unused name of exactly `len` characters in
+/// `parent`, trying combinations in odometer order (advancing the last
+/// character first, carrying over). If every name of `len` characters is
+/// already taken, falls back to `len + 1`. Fsyncs `parent` after the rename
+/// succeeds so the directory-entry change is flushed to disk.
+///
+/// On error, the second element of the error tuple carries the renamed path
+/// if (and only if) `fs::rename` itself already succeeded (i.e. only the
+/// post-rename directory fsync failed) — the caller must clean up under that
+/// path, not `from`, since `from` no longer exists.
+fn wipename_step(from: &Path, parent: &Path, len: usize) -> Result<PathBuf, (io::Error, Option<PathBuf>)> {
+    let mut indices = vec![0usize; len];
+    loop {
+        let name: String = indices.iter().map(|&i| WIPENAME_CHARS[i] as char).collect();
+        let candidate = parent.join(&name);
+        if fs::symlink_metadata(&candidate).is_ok() {
+            if !advance_name(&mut indices, WIPENAME_CHARS.len()) {
+                return wipename_step(from, parent, len + 1);
+            }
+            continue;
+        }
+
+        // Open (and later fsync) the parent directory *before* renaming, so
+        // a failure here (e.g. an empty relative parent) never leaves `from`
+        // renamed out from under the caller.
+        let dir = File::open(parent).map_err(|e| (e, None))?;
+        fs::rename(from, &candidate).map_err(|e| (e, None))?;
+        if let Err(e) = dir.sync_all() {
+            return Err((e, Some(candidate)));
+        }
+        return Ok(candidate);
+    }
+}
+
+/// GNU shred's `wipename` approach: repeatedly rename the file to a name one
+/// character shorter, starting at the original name's length and ending at
+/// length 1. Each step takes the first unused name of that length (growing
+/// only when every name of a length is taken), fsyncing the parent
+/// directory after each rename so the old directory entry is flushed out,
+/// then finally unlink. This scrubs the progressively-shorter name remnants
+/// left behind in the directory block.
+///
+/// On failure, returns the path the file was last successfully renamed to
+/// (not the original `path`, which no longer exists once renaming starts) so
+/// the caller can still find and remove it.
+fn rename_to_random_and_unlink(path: &Path) -> Result<(), (io::Error, PathBuf)> {
+    // A bare relative path like "file" has a parent of "" (not None), which
+    // File::open would reject with ENOENT; resolve that case to "." instead.
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let orig_name_len = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .map(|s| s.chars().count())
+        .unwrap_or(8)
+        .max(1);
+
+    let mut current = path.to_path_buf();
+    let mut len = orig_name_len;
+    while len >= 1 {
+        current = match wipename_step(&current, parent, len) {
+            Ok(renamed) => renamed,
+            Err((e, Some(renamed))) => return Err((e, renamed)),
+            Err((e, None)) => return Err((e, current)),
+        };
+        len -= 1;
+    }
+
+    fs::remove_file(&current).map_err(|e| (e, current))
+}
+
+/// Flags that control how a path is wiped, gathered together so the growing
+/// list of CLI switches doesn't have to be threaded through as separate
+/// positional parameters.
+#[derive(Debug, Clone, Copy)]
+struct WipeOptions {
+    require_confirm: bool,
+    recursive: bool,
+    exact: bool,
+    force: bool,
+    verbosity: Verbosity,
+}
+
+/// Shred a single regular file: overwrite, then rename-and-unlink.
+fn shred_file(path: &Path, passes: &[Pass], opts: &WipeOptions) -> io::Result<()> {
+    if opts.require_confirm {
         let prompt = format!("Securely delete file '{}' ?", path.display());
         if !ask_confirm(&prompt)? {
             println!("Skipping {}", path.display());
@@ -246,18 +617,19 @@ fn process_path(path: &Path, passes: usize, pattern: Pattern, require_confirm: b
 
     ensure_writable(path);
 
-    println!("Starting secure delete of {} ({} passes, pattern: {:?})", path.display(), passes, pattern);
-    overwrite_file(path, passes, pattern)?;
+    println!("Starting secure delete of {} ({} passes)", path.display(), passes.len());
+    overwrite_file(path, passes, opts.exact, opts.verbosity)?;
     // attempt rename & unlink
     match rename_to_random_and_unlink(path) {
         Ok(_) => {
             println!("Successfully removed {}", path.display());
             Ok(())
         }
-        Err(e) => {
+        Err((e, current_path)) => {
             eprintln!("Warning: overwrite succeeded but remove failed: {}", e);
-            // final attempt: try remove directly
-            if let Err(e2) = fs::remove_file(path) {
+            // final attempt: remove from wherever it was last renamed to, not
+            // the original path (which no longer exists after a rename).
+            if let Err(e2) = fs::remove_file(&current_path) {
                 return Err(e2);
             }
             Ok(())
@@ -265,6 +637,105 @@ fn process_path(path: &Path, passes: usize, pattern: Pattern, require_confirm: b
     }
 }
 
+/// Walk `dir` depth-first, shredding every regular file inside, then removing
+/// emptied subdirectories bottom-up. Individual file/directory failures are
+/// logged and do not abort the walk; returns `false` if anything failed.
+fn shred_directory(dir: &Path, passes: &[Pass], opts: &WipeOptions) -> bool {
+    let entries = match fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("Warning: failed to read directory {}: {}", dir.display(), e);
+            return false;
+        }
+    };
+
+    let mut all_ok = true;
+    let mut subdirs = Vec::new();
+    for entry in entries {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                eprintln!("Warning: failed to read entry in {}: {}", dir.display(), e);
+                all_ok = false;
+                continue;
+            }
+        };
+        let entry_path = entry.path();
+        // Use the entry's own (non-following) file type rather than
+        // `entry_path.is_dir()`, which stats through symlinks: a symlink to a
+        // directory outside the tree must never be recursed into, and a
+        // symlink to a file outside the tree must never be opened and
+        // overwritten through `shred_file`. Unlink the link itself instead.
+        let file_type = match entry.file_type() {
+            Ok(ft) => ft,
+            Err(e) => {
+                eprintln!("Warning: failed to stat {}: {}", entry_path.display(), e);
+                all_ok = false;
+                continue;
+            }
+        };
+        if file_type.is_symlink() {
+            if let Err(e) = fs::remove_file(&entry_path) {
+                eprintln!("Warning: failed to remove symlink {}: {}", entry_path.display(), e);
+                all_ok = false;
+            }
+        } else if file_type.is_dir() {
+            subdirs.push(entry_path);
+        } else if let Err(e) = shred_file(&entry_path, passes, opts) {
+            eprintln!("Warning: failed to shred {}: {}", entry_path.display(), e);
+            all_ok = false;
+        }
+    }
+
+    for subdir in subdirs {
+        if !shred_directory(&subdir, passes, opts) {
+            all_ok = false;
+        }
+    }
+
+    if let Err(e) = fs::remove_dir(dir) {
+        eprintln!("Warning: failed to remove directory {}: {}", dir.display(), e);
+        all_ok = false;
+    }
+
+    all_ok
+}
+
+fn process_path(path: &Path, passes: &[Pass], opts: &WipeOptions) -> io::Result<()> {
+    if !path.exists() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "file not found"));
+    }
+    if path.is_dir() {
+        if !opts.recursive {
+            return Err(io::Error::other(
+                "path is a directory; pass --recursive to shred directories",
+            ));
+        }
+        return if shred_directory(path, passes, opts) {
+            Ok(())
+        } else {
+            Err(io::Error::other(
+                "one or more entries under the directory failed to shred",
+            ))
+        };
+    }
+
+    if is_special_device(path)? {
+        if !opts.force {
+            return Err(io::Error::other(
+                "path is a block/character device; pass --force to wipe whole devices",
+            ));
+        }
+        ensure_writable(path);
+        println!("Starting secure wipe of device {} ({} passes)", path.display(), passes.len());
+        overwrite_device(path, passes, opts.verbosity)?;
+        println!("Finished wiping device {}", path.display());
+        return Ok(());
+    }
+
+    shred_file(path, passes, opts)
+}
+
 fn parse_usize_arg(it: &mut impl Iterator<Item = String>) -> Option<usize> {
     it.next().and_then(|s| s.parse::<usize>().ok())
 }
@@ -279,19 +750,31 @@ fn main() {
     // Defaults
     let mut passes = DEFAULT_PASSES;
     let mut pattern = Pattern::Random;
+    let mut scheme: Option<Scheme> = None;
+    let mut zero_final = false;
+    let mut recursive = false;
+    let mut exact = false;
+    let mut force = false;
+    let mut verbose = false;
+    let mut quiet = false;
     let mut require_confirm = false;
 
-    // parse positional first arg as file path; then parse options
-    // simple parser: first non-flag arg after program is file; supports single file only
+    // simple parser: leading non-flag args are paths (one or more); options follow
     let mut iter = args.into_iter();
     let _ = iter.next(); // skip program
 
-    let file_arg = match iter.next() {
-        Some(f) => f,
-        None => print_usage_and_exit(&program),
-    };
-
     let mut it = iter.peekable();
+    let mut path_args: Vec<String> = Vec::new();
+    while let Some(tok) = it.peek() {
+        if tok.starts_with('-') {
+            break;
+        }
+        path_args.push(it.next().unwrap());
+    }
+    if path_args.is_empty() {
+        print_usage_and_exit(&program);
+    }
+
     while let Some(tok) = it.next() {
         match tok.as_str() {
             "--passes" | "-p" => {
@@ -318,6 +801,36 @@ fn main() {
                     print_usage_and_exit(&program);
                 }
             }
+            "--scheme" => {
+                if let Some(v) = it.next() {
+                    if let Some(s) = Scheme::from_str(&v) {
+                        scheme = Some(s);
+                    } else {
+                        eprintln!("Unknown scheme: {} (use gutmann|dod|dod7|random)", v);
+                        print_usage_and_exit(&program);
+                    }
+                } else {
+                    print_usage_and_exit(&program);
+                }
+            }
+            "--zero" | "-z" => {
+                zero_final = true;
+            }
+            "--recursive" | "-r" => {
+                recursive = true;
+            }
+            "--exact" | "-x" => {
+                exact = true;
+            }
+            "--force" | "-f" => {
+                force = true;
+            }
+            "--verbose" | "-v" => {
+                verbose = true;
+            }
+            "--quiet" | "-q" => {
+                quiet = true;
+            }
             "--confirm" | "-c" => {
                 require_confirm = true;
             }
@@ -331,13 +844,40 @@ fn main() {
         }
     }
 
-    let path = PathBuf::from(file_arg);
+    let mut pass_specs = match scheme {
+        Some(s) => s.pass_specs(passes),
+        None => pass_specs_for_pattern(pattern, passes),
+    };
+    if zero_final {
+        pass_specs.push(Pass::unverified(PassSpec::FinalZero));
+    }
 
-    match process_path(&path, passes, pattern, require_confirm) {
-        Ok(_) => {}
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(2);
+    let verbosity = if quiet {
+        Verbosity::Quiet
+    } else if verbose {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    };
+
+    let opts = WipeOptions {
+        require_confirm,
+        recursive,
+        exact,
+        force,
+        verbosity,
+    };
+
+    let mut any_failed = false;
+    for path_arg in &path_args {
+        let path = PathBuf::from(path_arg);
+        if let Err(e) = process_path(&path, &pass_specs, &opts) {
+            eprintln!("Error: {}: {}", path.display(), e);
+            any_failed = true;
         }
     }
+
+    if any_failed {
+        std::process::exit(2);
+    }
 }